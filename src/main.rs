@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use axum::{
-    extract::{Multipart, Path, TypedHeader},
+    extract::{Multipart, Path, Query, TypedHeader},
     headers::ContentLength,
     routing::get,
 };
@@ -18,6 +18,38 @@ struct Config {
     dmca_email: String,
     size_limit: Option<u64>,
     cache: Option<usize>,
+    // When set, pastes are treated as opaque client-encrypted blobs: the server
+    // stores the ciphertext verbatim and never sees plaintext (zero-knowledge).
+    encrypted: Option<bool>,
+    // sqids codec tuning for the public slugs. Defaults to the sqids defaults.
+    alphabet: Option<String>,
+    min_length: Option<u8>,
+    blocklist: Option<Vec<String>>,
+    // Response compression quality; absent uses the `tower_http` default quality.
+    // Responses are compressed either way.
+    compression: Option<CompressionLevel>,
+}
+
+/// Compression quality knob mirroring `tower_http`'s own, but `Deserialize`able
+/// straight from the config file.
+#[derive(serde::Deserialize, Clone, Copy, Debug)]
+#[serde(rename_all = "lowercase")]
+enum CompressionLevel {
+    Fastest,
+    Best,
+    Default,
+    Precise(i32),
+}
+
+impl From<CompressionLevel> for tower_http::CompressionLevel {
+    fn from(level: CompressionLevel) -> Self {
+        match level {
+            CompressionLevel::Fastest => Self::Fastest,
+            CompressionLevel::Best => Self::Best,
+            CompressionLevel::Default => Self::Default,
+            CompressionLevel::Precise(quality) => Self::Precise(quality),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -27,12 +59,45 @@ struct State {
 }
 
 struct Cache {
-    data: dashmap::DashMap<String, String>,
+    // Cached pastes carry their submit-time language alongside the bytes so the
+    // HTML view highlights them identically whether or not it hit the cache.
+    data: dashmap::DashMap<String, (Vec<u8>, Option<String>)>,
     expiries: parking_lot::RwLock<
         std::collections::BinaryHeap<(chrono::DateTime<chrono::Local>, String)>,
     >,
 }
 
+/// Query parameters accepted by the HTML paste view.
+#[derive(serde::Deserialize)]
+struct ViewParams {
+    /// Syntax-highlight the paste as this language instead of the stored one.
+    lang: Option<String>,
+}
+
+/// How long a paste should live for, chosen per-upload.
+enum Expiration {
+    /// Expire a fixed duration after creation.
+    Duration(chrono::Duration),
+    /// Serve exactly once, then self-destruct on the next load.
+    BurnAfterReading,
+    /// Keep forever (the global sweeper leaves these alone).
+    Never,
+}
+
+impl Expiration {
+    /// Parse the `expires` multipart field / header. Accepts `never`, `burn`,
+    /// or an integer number of seconds.
+    fn parse(raw: &str) -> Result<Self, Error> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "never" => Ok(Self::Never),
+            "burn" => Ok(Self::BurnAfterReading),
+            secs => Ok(Self::Duration(chrono::Duration::seconds(
+                secs.parse().map_err(|_| Error::FieldInvalid)?,
+            ))),
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() {
     tracing_subscriber::fmt()
@@ -60,20 +125,40 @@ async fn main() {
     });
     let add_state = state.clone();
     let view_state = state.clone();
+    let del_state = state.clone();
+    let raw_state = state.clone();
     let deleter_state = state.clone();
     let add_cache = cache.clone();
     let view_cache = cache.clone();
+    let del_cache = cache.clone();
+    let raw_cache = cache.clone();
     let app = axum::Router::new()
         .route(
             "/",
-            get(root).post(move |typedheader, multipart| {
-                submit(typedheader, multipart, add_state, add_cache)
+            get(root).post(move |typedheader, headers, multipart| {
+                submit(typedheader, headers, multipart, add_state, add_cache)
             }),
         )
         .route(
             "/:path",
-            get(move |id| getpaste(id, view_state, view_cache)),
-        );
+            get(move |id, query| getpaste(id, query, view_state, view_cache))
+                .delete(move |id, headers| delete_paste(id, headers, del_state, del_cache)),
+        )
+        .route(
+            "/:path/raw",
+            get(move |id| getraw(id, raw_state, raw_cache)),
+        )
+        // Transparently gzip/brotli responses, and decompress gzip uploads
+        // before they reach `submit` (its own streaming cap guards against
+        // zip-bombs in the decompressed body).
+        .layer(tower_http::decompression::RequestDecompressionLayer::new())
+        .layer({
+            let mut layer = tower_http::compression::CompressionLayer::new();
+            if let Some(level) = config.compression {
+                layer = layer.quality(level.into());
+            }
+            layer
+        });
     tokio::spawn(async move { delete_expired(&deleter_state.db).await });
     tokio::spawn(async move { clear_cache(cache, config.cache).await });
     warn!("Listening on http://0.0.0.0:{} (http)", config.port);
@@ -85,50 +170,182 @@ async fn main() {
 
 axum_static_macro::static_file!(root, "index.html", axum_static_macro::content_types::HTML);
 
+/// Drain a multipart field into a byte buffer, refusing anything that would push
+/// it past `limit`. Reading in chunks keeps a decompressed zip-bomb from ever
+/// being fully buffered — the check fires before the offending chunk is kept.
+async fn read_capped(
+    mut field: axum::extract::multipart::Field<'_>,
+    limit: usize,
+) -> Result<Vec<u8>, Error> {
+    let mut buf = Vec::new();
+    while let Some(chunk) = field.chunk().await? {
+        if buf.len() + chunk.len() > limit {
+            return Err(Error::TooLarge);
+        }
+        buf.extend_from_slice(&chunk);
+    }
+    Ok(buf)
+}
+
+/// As [`read_capped`], but decodes the bounded bytes as UTF-8 for the small
+/// textual fields (`expires`, `lang`).
+async fn read_capped_text(
+    field: axum::extract::multipart::Field<'_>,
+    limit: usize,
+) -> Result<String, Error> {
+    String::from_utf8(read_capped(field, limit).await?).map_err(|_| Error::FieldInvalid)
+}
+
 async fn submit(
     TypedHeader(length): TypedHeader<ContentLength>,
+    headers: axum::http::HeaderMap,
     mut multipart: Multipart,
     state: State,
     cache: Arc<Cache>,
 ) -> Result<(axum::http::StatusCode, axum::http::HeaderMap, String), Error> {
-    if length.0 > state.config.size_limit.unwrap_or(1024) * 1024 {
+    let limit = (state.config.size_limit.unwrap_or(1024) * 1024) as usize;
+    // Cheap fast-path: the compressed wire length already blows the budget.
+    if length.0 as usize > limit {
         return Ok((
             axum::http::StatusCode::PAYLOAD_TOO_LARGE,
             axum::http::HeaderMap::new(),
             "Paste too long!".to_string(),
         ));
     }
-    let mut data = String::new();
+    let mut data = Vec::new();
+    let mut expires_raw: Option<String> = None;
+    let mut language: Option<String> = None;
     while let Some(field) = multipart.next_field().await? {
-        if field.name().ok_or(Error::FieldInvalid)? == "contents" {
-            data = field.text().await?;
-            break;
+        // Every field we keep is drained through the same capped reader so a zip-bomb
+        // can't inflate past the limit after `RequestDecompressionLayer` — the
+        // `expires` and `lang` fields are just as exposed to the bypass as the
+        // body, so none of them may use the unbounded `field.text()`.
+        match field.name().ok_or(Error::FieldInvalid)? {
+            "contents" => data = read_capped(field, limit).await?,
+            "expires" => expires_raw = Some(read_capped_text(field, limit).await?),
+            "lang" => language = Some(read_capped_text(field, limit).await?),
+            _ => {}
         }
     }
 
-    let persistence_length = chrono::Duration::weeks(1);
-    let expires = chrono::offset::Local::now()
-        .checked_add_signed(persistence_length)
-        .ok_or(Error::TimeError)?;
-    let key = random_string::generate(
-        8,
+    let expiration = match expires_raw {
+        Some(raw) => Expiration::parse(&raw)?,
+        None => match headers.get("expires") {
+            Some(value) => Expiration::parse(value.to_str().map_err(|_| Error::FieldInvalid)?)?,
+            // Preserve the historical default of a one-week lifetime.
+            None => Expiration::Duration(chrono::Duration::weeks(1)),
+        },
+    };
+    let now = chrono::offset::Local::now();
+    // `expires` is NULL for pastes that never expire; burn pastes carry no TTL
+    // until they are read, when their contents are blanked and the row is left
+    // for the sweeper.
+    let (expires, burn): (Option<chrono::DateTime<chrono::Local>>, bool) = match expiration {
+        Expiration::Duration(length) => {
+            (Some(now.checked_add_signed(length).ok_or(Error::TimeError)?), false)
+        }
+        Expiration::BurnAfterReading => (None, true),
+        Expiration::Never => (None, false),
+    };
+    let db = &state.db;
+    // Reserve the row's serial id up front so we can derive a collision-free
+    // slug from it without a second round-trip or a birthday-collision retry.
+    let id = query!("SELECT nextval(pg_get_serial_sequence('pastes', 'id')) AS id")
+        .fetch_one(db)
+        .await?
+        .id
+        .ok_or(Error::InternalError)?;
+    let key = build_sqids(&state.config)?.encode(&[id as u64])?;
+    // High-entropy token handed back to the creator so they can delete the
+    // paste later; only its hash ever touches the database.
+    let admin = random_string::generate(
+        32,
         "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz1234567890",
     );
-    let db = &state.db;
-    // TODO check if paste already exists
-    let contents = html_escape::encode_text(&data);
+    let admin_hash = sha256(admin.as_bytes());
+    // In zero-knowledge mode the field is an already-encrypted blob we must not
+    // touch; otherwise we validate the plaintext as UTF-8 but store it verbatim.
+    // Escaping happens at render time so the raw view stays byte-exact and the
+    // syntax highlighter sees real source rather than pre-escaped entities.
+    let contents: Vec<u8> = if state.config.encrypted.unwrap_or(false) {
+        data
+    } else {
+        String::from_utf8(data)
+            .map_err(|_| Error::FieldInvalid)?
+            .into_bytes()
+    };
+    // Content-addressed dedup: if a live, identical paste already exists, reuse
+    // it rather than storing another copy under a fresh key. Burn pastes are
+    // one-shot by definition and are never deduplicated. Dedup is also skipped
+    // entirely in zero-knowledge mode: identical ciphertext hashes identically,
+    // so a 302 onto an existing key would be a content-equality oracle letting
+    // an observer confirm a given blob is already stored here.
+    let hash = sha256(&contents);
+    if !burn && !state.config.encrypted.unwrap_or(false) {
+        // Never dedup onto a burn paste: an unconsumed one carries a NULL expiry
+        // (so it would otherwise look like it lives forever) but must only ever
+        // be read by the single person it was shared with.
+        if let Some(existing) = query!(
+            "SELECT key, expires FROM pastes WHERE hash = $1 AND burn = false",
+            &hash
+        )
+        .fetch_optional(db)
+        .await?
+        {
+            let live = existing.expires.map_or(true, |e| e > now);
+            if live {
+                // Keep the surviving copy around at least as long as this
+                // request asks for.
+                match (expires, existing.expires) {
+                    (None, Some(_)) => {
+                        query!("UPDATE pastes SET expires = NULL WHERE key = $1", existing.key)
+                            .execute(db)
+                            .await?;
+                    }
+                    (Some(wanted), Some(current)) if wanted > current => {
+                        query!(
+                            "UPDATE pastes SET expires = $1 WHERE key = $2",
+                            wanted,
+                            existing.key
+                        )
+                        .execute(db)
+                        .await?;
+                    }
+                    _ => {}
+                }
+                let mut headers = axum::http::HeaderMap::new();
+                headers.insert(
+                    axum::http::header::LOCATION,
+                    axum::http::header::HeaderValue::from_str(&format!("/{}", existing.key))?,
+                );
+                return Ok((
+                    axum::http::StatusCode::FOUND,
+                    headers,
+                    "Paste submitted!".to_string(),
+                ));
+            }
+        }
+    }
     query!(
-        "INSERT INTO pastes VALUES ($1, $2, $3)",
+        "INSERT INTO pastes (id, key, contents, expires, burn, admin, hash, language) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+        id,
         key,
         &contents,
-        expires
+        expires,
+        burn,
+        &admin_hash,
+        &hash,
+        language.as_deref()
     )
     .execute(db)
     .await?;
-    if let Some(_) = state.config.cache {
+    // Burn pastes must be served from the database so the read-and-delete is
+    // transactional, so we never populate the cache with them.
+    if state.config.cache.is_some() && !burn {
         let mut heap = cache.expiries.write();
 
-        cache.data.insert(key.clone(), contents.into_owned());
+        cache.data.insert(key.clone(), (contents, language));
         heap.push((chrono::offset::Local::now(), key.clone()));
     }
 
@@ -137,6 +354,10 @@ async fn submit(
         axum::http::header::LOCATION,
         axum::http::header::HeaderValue::from_str(&format!("/{}", key))?,
     );
+    headers.insert(
+        "X-Deletion-Token",
+        axum::http::header::HeaderValue::from_str(&admin)?,
+    );
     Ok((
         axum::http::StatusCode::FOUND,
         headers,
@@ -144,39 +365,126 @@ async fn submit(
     ))
 }
 
-async fn getpaste(
-    Path(id): Path<String>,
-    state: State,
-    cache: Arc<Cache>,
-) -> Result<(axum::http::StatusCode, axum::http::HeaderMap, String), Error> {
-    let mut contents = String::new();
-    if let Some(_) = state.config.cache {
-        if let Some(item) = cache.data.get(&id) {
-            contents = item.to_string();
+/// A paste lookup result, shared by the HTML and raw views.
+enum Fetched {
+    Found {
+        contents: Vec<u8>,
+        language: Option<String>,
+    },
+    Missing,
+    Burned,
+}
+
+/// Resolve a slug to its (possibly cached) contents, applying burn-after-reading
+/// semantics transactionally. This is the common read path behind `getpaste`
+/// and `getraw`.
+async fn fetch_paste(slug: &str, state: &State, cache: &Arc<Cache>) -> Result<Fetched, Error> {
+    // A configured cache only ever holds non-burn pastes, so a hit can be served
+    // straight away; a miss (an evicted paste, or a burn paste that is
+    // deliberately never cached) falls through to the transactional database
+    // read below, which is what makes burn-after-reading work even with a cache.
+    if state.config.cache.is_some() {
+        if let Some(item) = cache.data.get(slug) {
             trace!("Cache hit!");
+            let (contents, language) = item.value();
+            return Ok(Fetched::Found {
+                contents: contents.clone(),
+                language: language.clone(),
+            });
         }
-    } else {
-        let db = &state.db;
-        let res = match query!("SELECT contents FROM pastes WHERE key = $1", id)
-            .fetch_one(db)
-            .await
+    }
+    let db = &state.db;
+    // Resolve the slug to a primary key. A canonical sqids slug decodes straight
+    // back to its id, but `decode` is not injective — an arbitrary string can
+    // decode to an unrelated live id — so we only trust a decode that re-encodes
+    // to the exact same slug. Everything else (a legacy random key minted before
+    // the sqids migration, or a non-canonical string) is looked up against the
+    // `key` column, so old URLs keep resolving and a stale slug can never alias
+    // another paste.
+    let sqids = build_sqids(&state.config)?;
+    let id = match sqids.decode(slug).as_slice() {
+        [id] if sqids.encode(&[*id])? == slug => *id as i64,
+        _ => match query!("SELECT id FROM pastes WHERE key = $1", slug)
+            .fetch_optional(db)
+            .await?
         {
-            Ok(data) => data,
-            Err(sqlx::Error::RowNotFound) => {
-                let mut headers = axum::http::HeaderMap::new();
-                headers.insert(
-                    axum::http::header::CONTENT_TYPE,
-                    axum::http::header::HeaderValue::from_static("text/html"),
-                );
-                return Ok((
-                    axum::http::StatusCode::NOT_FOUND,
-                    headers,
-                    include_str!("./404.html").to_string(),
-                ));
+            Some(row) => row.id,
+            None => return Ok(Fetched::Missing),
+        },
+    };
+    let mut tx = db.begin().await?;
+    // Lock the row for the duration of the transaction so two concurrent reads
+    // of the same burn paste can't both observe live contents and serve the body
+    // twice — the second waits for the first to blank it and commit.
+    let res = match query!(
+        "SELECT contents, burn, language FROM pastes WHERE id = $1 FOR UPDATE",
+        id
+    )
+    .fetch_one(&mut *tx)
+    .await
+    {
+        Ok(data) => data,
+        Err(sqlx::Error::RowNotFound) => {
+            tx.rollback().await?;
+            return Ok(Fetched::Missing);
+        }
+        Err(e) => return Err(Error::Sqlx(e)),
+    };
+    if res.burn {
+        match res.contents {
+            // The one permitted view already happened; the row lingers only as a
+            // tombstone until the sweeper reaps it.
+            None => {
+                tx.commit().await?;
+                return Ok(Fetched::Burned);
             }
-            Err(e) => return Err(Error::Sqlx(e)),
-        };
-        contents = res.contents.ok_or(Error::InternalError)?;
+            // Consume it: blank the contents and mark it for the next sweep.
+            Some(_) => {
+                query!(
+                    "UPDATE pastes SET contents = NULL, expires = $1 WHERE id = $2",
+                    chrono::offset::Local::now(),
+                    id
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+    }
+    tx.commit().await?;
+    Ok(Fetched::Found {
+        contents: res.contents.ok_or(Error::InternalError)?,
+        language: res.language,
+    })
+}
+
+async fn getpaste(
+    Path(slug): Path<String>,
+    Query(params): Query<ViewParams>,
+    state: State,
+    cache: Arc<Cache>,
+) -> Result<(axum::http::StatusCode, axum::http::HeaderMap, Vec<u8>), Error> {
+    // In zero-knowledge mode the primary view is a static bootstrap page: it
+    // fetches the opaque ciphertext from the raw view and decrypts it with the
+    // key from the URL fragment, which never reaches us. We must serve it
+    // *before* reading the paste so that loading the page doesn't itself consume
+    // a burn-after-reading paste — that happens once, when the bootstrap fetches
+    // the raw view.
+    if state.config.encrypted.unwrap_or(false) {
+        let mut headers = axum::http::HeaderMap::new();
+        headers.insert(
+            axum::http::header::CONTENT_TYPE,
+            axum::http::header::HeaderValue::from_static("text/html"),
+        );
+        return Ok((
+            axum::http::StatusCode::OK,
+            headers,
+            include_str!("./encrypted.html").to_string().into_bytes(),
+        ));
+    }
+    let (contents, language) = match fetch_paste(&slug, &state, &cache).await? {
+        Fetched::Found { contents, language } => (contents, language),
+        Fetched::Missing => return Ok(not_found(include_str!("./404.html"))),
+        Fetched::Burned => return Ok(not_found(include_str!("./burned.html"))),
     };
     let mut headers = axum::http::HeaderMap::new();
     headers.insert(
@@ -189,19 +497,177 @@ async fn getpaste(
     let html = tokio::fs::read_to_string("./src/paste.html")
         .await
         .expect("Program is in debug mode and the paste.html file was not found!");
-    // This has both \n and \r\n to normalize for HTTP weirdness
-    let clean_contents = contents.replace("\r\n", "<br>").replace("\n", "<br>");
+    let contents = String::from_utf8_lossy(&contents);
+    // An explicit `?lang=` query wins over the language chosen at submit time;
+    // without either we fall back to the naive newline-to-<br> substitution.
+    let body = match params.lang.or(language) {
+        Some(lang) => highlight(&contents, &lang)?,
+        // No highlighting: escape the raw source, then turn newlines into <br>.
+        // This has both \n and \r\n to normalize for HTTP weirdness.
+        None => html_escape::encode_text(&contents)
+            .replace("\r\n", "<br>")
+            .replace("\n", "<br>"),
+    };
     let final_contents = html
         .replace("%{dmca_email}%", &state.config.dmca_email)
-        .replace("%{paste_contents}%", &clean_contents);
-    Ok((axum::http::StatusCode::OK, headers, final_contents))
+        .replace("%{paste_contents}%", &body);
+    Ok((
+        axum::http::StatusCode::OK,
+        headers,
+        final_contents.into_bytes(),
+    ))
+}
+
+async fn getraw(
+    Path(slug): Path<String>,
+    state: State,
+    cache: Arc<Cache>,
+) -> Result<(axum::http::StatusCode, axum::http::HeaderMap, Vec<u8>), Error> {
+    match fetch_paste(&slug, &state, &cache).await? {
+        Fetched::Found { contents, .. } => {
+            let mut headers = axum::http::HeaderMap::new();
+            // Zero-knowledge pastes are opaque ciphertext, so the raw view hands
+            // them back as a binary blob for the bootstrap client to decrypt.
+            let content_type = if state.config.encrypted.unwrap_or(false) {
+                "application/octet-stream"
+            } else {
+                "text/plain; charset=utf-8"
+            };
+            headers.insert(
+                axum::http::header::CONTENT_TYPE,
+                axum::http::header::HeaderValue::from_static(content_type),
+            );
+            Ok((axum::http::StatusCode::OK, headers, contents))
+        }
+        Fetched::Missing => Ok(not_found(include_str!("./404.html"))),
+        Fetched::Burned => Ok(not_found(include_str!("./burned.html"))),
+    }
+}
+
+/// Build the sqids codec from the configured alphabet, minimum length, and
+/// profanity blocklist, falling back to the sqids defaults where unset. The
+/// first successfully built codec is kept and reused — it is the same for every
+/// request, and building it on each call is pure waste.
+fn build_sqids(config: &Config) -> Result<&'static sqids::Sqids, Error> {
+    static SQIDS: std::sync::OnceLock<sqids::Sqids> = std::sync::OnceLock::new();
+    if let Some(sqids) = SQIDS.get() {
+        return Ok(sqids);
+    }
+    let mut builder = sqids::Sqids::builder();
+    if let Some(alphabet) = &config.alphabet {
+        builder = builder.alphabet(alphabet.chars().collect());
+    }
+    if let Some(min_length) = config.min_length {
+        builder = builder.min_length(min_length);
+    }
+    if let Some(blocklist) = &config.blocklist {
+        builder = builder.blocklist(blocklist.iter().cloned().collect());
+    }
+    // Build outside `get_or_init` so a misconfiguration surfaces as an error
+    // rather than being cached; a benign race just discards the loser's copy.
+    let built = builder.build()?;
+    Ok(SQIDS.get_or_init(|| built))
+}
+
+/// The embedded syntax definitions, deserialized once on first use. The dump is
+/// multiple megabytes and hundreds of syntaxes, so reloading it per request is a
+/// cheap denial-of-service vector.
+fn syntax_set() -> &'static syntect::parsing::SyntaxSet {
+    static SYNTAXES: std::sync::OnceLock<syntect::parsing::SyntaxSet> = std::sync::OnceLock::new();
+    SYNTAXES.get_or_init(syntect::parsing::SyntaxSet::load_defaults_newlines)
+}
+
+/// Syntax-highlight `source` as `language`, emitting `<span>`-classed HTML.
+/// Unknown languages fall back to plain text so the view never fails hard.
+fn highlight(source: &str, language: &str) -> Result<String, Error> {
+    use syntect::util::LinesWithEndings;
+    let syntaxes = syntax_set();
+    let syntax = syntaxes
+        .find_syntax_by_token(language)
+        .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+    let mut generator = syntect::html::ClassedHTMLGenerator::new_with_class_style(
+        syntax,
+        syntaxes,
+        syntect::html::ClassStyle::Spaced,
+    );
+    for line in LinesWithEndings::from(source) {
+        generator.parse_html_for_line_which_includes_newline(line)?;
+    }
+    Ok(generator.finalize())
+}
+
+/// SHA-256 of some bytes, used to hash deletion tokens before storage.
+fn sha256(bytes: &[u8]) -> Vec<u8> {
+    use sha2::Digest;
+    let mut hasher = sha2::Sha256::new();
+    hasher.update(bytes);
+    hasher.finalize().to_vec()
+}
+
+/// Compare two byte slices without leaking their relationship through timing.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
+async fn delete_paste(
+    Path(id): Path<String>,
+    headers: axum::http::HeaderMap,
+    state: State,
+    cache: Arc<Cache>,
+) -> Result<axum::http::StatusCode, Error> {
+    let token = headers
+        .get("X-Deletion-Token")
+        .ok_or(Error::Unauthorized)?
+        .to_str()
+        .map_err(|_| Error::Unauthorized)?;
+    let db = &state.db;
+    let row = query!("SELECT admin FROM pastes WHERE key = $1", id)
+        .fetch_optional(db)
+        .await?
+        .ok_or(Error::NotFound)?;
+    let stored = row.admin.ok_or(Error::InternalError)?;
+    if !constant_time_eq(&stored, &sha256(token.as_bytes())) {
+        return Err(Error::Unauthorized);
+    }
+    query!("DELETE FROM pastes WHERE key = $1", id)
+        .execute(db)
+        .await?;
+    if state.config.cache.is_some() {
+        cache.data.remove(&id);
+        let mut heap = cache.expiries.write();
+        let retained = heap.drain().filter(|(_, key)| *key != id).collect();
+        *heap = retained;
+    }
+    Ok(axum::http::StatusCode::NO_CONTENT)
+}
+
+/// Build a 404 response from a pre-included HTML body.
+fn not_found(body: &str) -> (axum::http::StatusCode, axum::http::HeaderMap, Vec<u8>) {
+    let mut headers = axum::http::HeaderMap::new();
+    headers.insert(
+        axum::http::header::CONTENT_TYPE,
+        axum::http::header::HeaderValue::from_static("text/html"),
+    );
+    (
+        axum::http::StatusCode::NOT_FOUND,
+        headers,
+        body.to_string().into_bytes(),
+    )
 }
 
 async fn delete_expired(db: &sqlx::PgPool) {
     loop {
         info!("Deleting old pastes...");
         let now: chrono::DateTime<chrono::Local> = chrono::Local::now();
-        match query!("DELETE FROM pastes WHERE expires < $1", now)
+        // NULL expiry means "never" — leave those rows untouched.
+        match query!("DELETE FROM pastes WHERE expires IS NOT NULL AND expires < $1", now)
             .execute(db)
             .await
         {
@@ -220,7 +686,7 @@ async fn clear_cache(cache: Arc<Cache>, max: Option<usize>) {
             loop {
                 let mut size: usize = 0;
                 for item in cache.data.iter() {
-                    size += item.value().capacity();
+                    size += item.value().0.capacity();
                 }
                 if size > max_size * 1_048_576 {
                     if let Some(item) = heap.peek() {
@@ -242,9 +708,26 @@ enum Error {
     TimeError,
     FieldInvalid,
     InternalError,
+    Unauthorized,
+    NotFound,
+    TooLarge,
     InvalidHeaderValue(axum::http::header::InvalidHeaderValue),
     Sqlx(sqlx::Error),
     Multipart(axum::extract::multipart::MultipartError),
+    Sqids(sqids::Error),
+    Syntect(syntect::Error),
+}
+
+impl From<sqids::Error> for Error {
+    fn from(e: sqids::Error) -> Self {
+        Self::Sqids(e)
+    }
+}
+
+impl From<syntect::Error> for Error {
+    fn from(e: syntect::Error) -> Self {
+        Self::Syntect(e)
+    }
 }
 
 impl From<axum::http::header::InvalidHeaderValue> for Error {
@@ -277,6 +760,15 @@ impl axum::response::IntoResponse for Error {
                 "Unknown internal error".into(),
                 axum::http::StatusCode::INTERNAL_SERVER_ERROR,
             ),
+            Error::Unauthorized => (
+                "Invalid deletion token".into(),
+                axum::http::StatusCode::FORBIDDEN,
+            ),
+            Error::NotFound => ("Paste not found".into(), axum::http::StatusCode::NOT_FOUND),
+            Error::TooLarge => (
+                "Paste too long!".into(),
+                axum::http::StatusCode::PAYLOAD_TOO_LARGE,
+            ),
             Error::InvalidHeaderValue(_) => (
                 "Invalid redirect value (this should be impossible)".into(),
                 axum::http::StatusCode::INTERNAL_SERVER_ERROR,
@@ -289,6 +781,14 @@ impl axum::response::IntoResponse for Error {
                 "MultiPartFormData invalid".into(),
                 axum::http::StatusCode::BAD_REQUEST,
             ),
+            Error::Sqids(_) => (
+                "Slug encoding failed".into(),
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+            Error::Syntect(_) => (
+                "Syntax highlighting failed".into(),
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            ),
         };
         warn!("{:?}", self);
         axum::response::Response::builder()